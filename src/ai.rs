@@ -0,0 +1,400 @@
+//! Optional AI-control subsystem: ships are flown by small neural networks
+//! that are evolved across generations by a genetic algorithm instead of
+//! being hand-tuned. Disabled unless [`AiSettings::enabled`] is set.
+
+use bevy::ecs::schedule::common_conditions::{in_state, resource_exists};
+use bevy::prelude::*;
+use bevy_turborand::prelude::*;
+use nalgebra::DMatrix;
+
+use crate::{physics, Asteroid, Ship, Velocity, PLAYER_SIZE, PLAYER_TURN_SPEED};
+
+/// How many of the nearest asteroids are reported to the network each tick.
+const SENSED_ASTEROIDS: usize = 5;
+
+/// velocity (x, y) + heading + distance/bearing to each sensed asteroid.
+const INPUT_SIZE: usize = 3 + SENSED_ASTEROIDS * 2;
+const HIDDEN_SIZE: usize = 12;
+/// angle delta, thrusting, shoot_requested.
+const OUTPUT_SIZE: usize = 3;
+
+const GENERATION_SECONDS: f32 = 30.0;
+/// Fraction of the population kept as breeding parents each generation.
+const SURVIVOR_FRACTION: f32 = 0.25;
+
+/// Tunables for the evolving population, exposed so they can be tweaked
+/// without touching code.
+#[derive(Resource)]
+pub struct AiSettings {
+    pub enabled: bool,
+    pub population_size: usize,
+    pub mutation_rate: f32,
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            population_size: 20,
+            mutation_rate: 0.05,
+        }
+    }
+}
+
+/// Parses `--ai` from argv to enable the evolving population, mirroring
+/// [`crate::net::parse_net_args`]'s "absent unless explicitly passed" pattern
+/// — without it the subsystem would be dead code nobody could ever reach.
+fn parse_ai_enabled() -> bool {
+    std::env::args().any(|arg| arg == "--ai")
+}
+
+/// A small feed-forward network: `weights[i]` maps layer `i`'s activations
+/// (with an appended bias term) to layer `i + 1`.
+#[derive(Clone)]
+struct NN {
+    weights: Vec<DMatrix<f32>>,
+}
+
+impl NN {
+    fn new(config: &[usize], rng: &mut RngComponent) -> Self {
+        let weights = config
+            .windows(2)
+            .map(|layer_sizes| {
+                let (fan_in, fan_out) = (layer_sizes[0], layer_sizes[1]);
+                let scale = (2.0 / fan_in as f32).sqrt();
+                DMatrix::from_fn(fan_out, fan_in + 1, |_, _| standard_normal(rng) * scale)
+            })
+            .collect();
+        Self { weights }
+    }
+
+    fn forward(&self, input: &[f32]) -> Vec<f32> {
+        let mut activations = DMatrix::from_column_slice(input.len(), 1, input);
+        let last_layer = self.weights.len() - 1;
+        for (i, layer) in self.weights.iter().enumerate() {
+            let augmented = activations.insert_row(activations.nrows(), 1.0);
+            let mut next = layer * augmented;
+            if i == last_layer {
+                next.apply(|v| *v = v.tanh());
+            } else {
+                next.apply(|v| *v = v.max(0.0));
+            }
+            activations = next;
+        }
+        activations.iter().copied().collect()
+    }
+
+    fn crossover(a: &NN, b: &NN) -> NN {
+        let weights = a
+            .weights
+            .iter()
+            .zip(&b.weights)
+            .map(|(wa, wb)| (wa + wb) * 0.5)
+            .collect();
+        NN { weights }
+    }
+
+    fn mutate(&mut self, mutation_rate: f32, rng: &mut RngComponent) {
+        for layer in self.weights.iter_mut() {
+            for weight in layer.iter_mut() {
+                if rng.f32() < mutation_rate {
+                    *weight = standard_normal(rng);
+                }
+            }
+        }
+    }
+}
+
+/// Box-Muller transform: turborand only hands out uniform samples.
+fn standard_normal(rng: &mut RngComponent) -> f32 {
+    let u1 = rng.f32().max(f32::EPSILON);
+    let u2 = rng.f32();
+    (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+}
+
+fn network_config() -> [usize; 3] {
+    [INPUT_SIZE, HIDDEN_SIZE, OUTPUT_SIZE]
+}
+
+/// The evolving gene pool. Fitness is not stored here: each ship's
+/// [`AiPilot`] tracks it against its `brain_index` for the current generation.
+#[derive(Resource)]
+struct Population {
+    brains: Vec<NN>,
+    generation: u32,
+    timer: Timer,
+}
+
+impl Population {
+    fn seeded(size: usize, rng: &mut RngComponent) -> Self {
+        Self {
+            brains: (0..size).map(|_| NN::new(&network_config(), rng)).collect(),
+            generation: 0,
+            timer: Timer::from_seconds(GENERATION_SECONDS, TimerMode::Once),
+        }
+    }
+}
+
+/// Tags a ship as AI-piloted and tracks the fitness it has accrued so far.
+#[derive(Component)]
+pub(crate) struct AiPilot {
+    brain_index: usize,
+    fitness: f32,
+    dead: bool,
+}
+
+#[derive(Bundle)]
+struct AiShipBundle {
+    ship: Ship,
+    position: TransformBundle,
+    velocity: Velocity,
+    mass: physics::Mass,
+    force: physics::Force,
+    pilot: AiPilot,
+}
+
+fn spawn_population(
+    mut commands: Commands,
+    settings: Res<AiSettings>,
+    mut global_rng: ResMut<GlobalRng>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let mut rng = RngComponent::from(&mut global_rng);
+    let population = Population::seeded(settings.population_size, &mut rng);
+    spawn_generation(&mut commands, &population);
+    commands.insert_resource(population);
+}
+
+fn spawn_generation(commands: &mut Commands, population: &Population) {
+    for (brain_index, _) in population.brains.iter().enumerate() {
+        commands.spawn(AiShipBundle {
+            ship: Ship::default(),
+            position: TransformBundle::default(),
+            velocity: Velocity(Vec2::ZERO),
+            mass: physics::Mass::from_radius(PLAYER_SIZE),
+            force: physics::Force::default(),
+            pilot: AiPilot {
+                brain_index,
+                fitness: 0.0,
+                dead: false,
+            },
+        });
+    }
+}
+
+/// Feeds each living AI ship its sensor vector and applies the network's
+/// output in place of keyboard input.
+fn pilot_ships(
+    settings: Res<AiSettings>,
+    population: Res<Population>,
+    physics_config: Res<physics::PhysicsConfig>,
+    mut ships: Query<(
+        &mut Ship,
+        &Velocity,
+        &mut physics::Force,
+        &Transform,
+        &mut AiPilot,
+    )>,
+    asteroids: Query<&Transform, With<Asteroid>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    for (mut ship, velocity, mut force, transform, mut pilot) in ships.iter_mut() {
+        if pilot.dead {
+            continue;
+        }
+        pilot.fitness += crate::FIXED_TIMESTEP;
+
+        let mut nearest: Vec<Vec2> = asteroids
+            .iter()
+            .map(|t| t.translation.truncate() - transform.translation.truncate())
+            .collect();
+        nearest.sort_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+        nearest.truncate(SENSED_ASTEROIDS);
+
+        let mut input = Vec::with_capacity(INPUT_SIZE);
+        input.push(velocity.0.x);
+        input.push(velocity.0.y);
+        input.push(ship.angle);
+        for offset in &nearest {
+            input.push(offset.length());
+            input.push(offset.y.atan2(offset.x));
+        }
+        input.resize(INPUT_SIZE, 0.0);
+
+        let output = population.brains[pilot.brain_index].forward(&input);
+        ship.angle += output[0] * PLAYER_TURN_SPEED;
+        ship.thrusting = output[1] > 0.0;
+        ship.shoot_requested = output[2] > 0.0;
+        if ship.thrusting {
+            force.0 +=
+                Vec2::new(ship.angle.cos(), ship.angle.sin()) * physics_config.thrust_force;
+        }
+    }
+}
+
+/// Marks a ship's pilot dead and freezes it in place so its final fitness
+/// can still be harvested when the generation ends.
+pub(crate) fn kill_ai_pilot(pilot: &mut AiPilot, velocity: &mut Velocity) {
+    pilot.dead = true;
+    velocity.0 = Vec2::ZERO;
+}
+
+/// Credits an asteroid kill to this pilot's fitness (survival time plus
+/// score), kept separate from the human player's `Game.score` so AI
+/// evaluation rewards actually destroying asteroids without leaking into the
+/// displayed score.
+pub(crate) fn credit_kill(pilot: &mut AiPilot, points: i32) {
+    if !pilot.dead {
+        pilot.fitness += points as f32;
+    }
+}
+
+fn evolve_population(
+    mut commands: Commands,
+    settings: Res<AiSettings>,
+    mut population: ResMut<Population>,
+    pilots: Query<(Entity, &AiPilot)>,
+    mut global_rng: ResMut<GlobalRng>,
+    time: Res<Time>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    population.timer.tick(time.delta());
+    let all_dead = pilots.iter().all(|(_, pilot)| pilot.dead);
+    if !population.timer.finished() && !all_dead {
+        return;
+    }
+
+    let mut ranked: Vec<(usize, f32)> = pilots
+        .iter()
+        .map(|(_, pilot)| (pilot.brain_index, pilot.fitness))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let survivor_count = ((ranked.len() as f32 * SURVIVOR_FRACTION).ceil() as usize)
+        .max(2)
+        .min(ranked.len().max(2));
+    let parents: Vec<NN> = ranked
+        .iter()
+        .take(survivor_count)
+        .map(|(brain_index, _)| population.brains[*brain_index].clone())
+        .collect();
+
+    let mut rng = RngComponent::from(&mut global_rng);
+    let mut next_generation = Vec::with_capacity(settings.population_size);
+    for i in 0..settings.population_size {
+        let a = &parents[i % parents.len()];
+        let b = &parents[(i + 1) % parents.len()];
+        let mut child = NN::crossover(a, b);
+        child.mutate(settings.mutation_rate, &mut rng);
+        next_generation.push(child);
+    }
+
+    for (entity, _) in pilots.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    population.brains = next_generation;
+    population.generation += 1;
+    population.timer.reset();
+
+    spawn_generation(&mut commands, &population);
+}
+
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AiSettings {
+            enabled: parse_ai_enabled(),
+            ..Default::default()
+        })
+            .add_startup_system(spawn_population)
+            .add_system(
+                pilot_ships
+                    .in_schedule(CoreSchedule::FixedUpdate)
+                    .before(crate::GameplaySet)
+                    // Read-only budget check: `GameplaySet`'s own condition is
+                    // what counts the step, so each fixed step is counted once.
+                    .run_if(|steps: Res<crate::FixedStepsThisFrame>| {
+                        steps.0 < crate::MAX_FIXED_STEPS_PER_FRAME
+                    })
+                    .run_if(resource_exists::<Population>())
+                    .run_if(in_state(crate::GameState::Playing)),
+            )
+            .add_system(
+                evolve_population
+                    .run_if(resource_exists::<Population>())
+                    .run_if(in_state(crate::GameState::Playing)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rng() -> RngComponent {
+        RngComponent::from(&mut GlobalRng::new())
+    }
+
+    #[test]
+    fn forward_produces_one_output_per_output_neuron_in_tanh_range() {
+        let mut r = rng();
+        let nn = NN::new(&network_config(), &mut r);
+        let input = vec![0.0; INPUT_SIZE];
+        let output = nn.forward(&input);
+        assert_eq!(output.len(), OUTPUT_SIZE);
+        for value in output {
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn crossover_averages_parent_weights() {
+        let mut r = rng();
+        let a = NN::new(&network_config(), &mut r);
+        let b = NN::new(&network_config(), &mut r);
+        let child = NN::crossover(&a, &b);
+        for ((child_layer, a_layer), b_layer) in
+            child.weights.iter().zip(&a.weights).zip(&b.weights)
+        {
+            for ((&c, &wa), &wb) in child_layer.iter().zip(a_layer).zip(b_layer) {
+                assert!((c - (wa + wb) * 0.5).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn mutate_with_zero_rate_leaves_weights_unchanged() {
+        let mut r = rng();
+        let original = NN::new(&network_config(), &mut r);
+        let mut mutated = original.clone();
+        mutated.mutate(0.0, &mut r);
+        for (orig_layer, mutated_layer) in original.weights.iter().zip(&mutated.weights) {
+            assert_eq!(orig_layer, mutated_layer);
+        }
+    }
+
+    #[test]
+    fn mutate_with_full_rate_changes_every_weight() {
+        let mut r = rng();
+        let original = NN::new(&network_config(), &mut r);
+        let mut mutated = original.clone();
+        mutated.mutate(1.0, &mut r);
+        let mut any_unchanged = false;
+        for (orig_layer, mutated_layer) in original.weights.iter().zip(&mutated.weights) {
+            for (&orig, &new) in orig_layer.iter().zip(mutated_layer) {
+                if orig == new {
+                    any_unchanged = true;
+                }
+            }
+        }
+        assert!(!any_unchanged);
+    }
+}
@@ -3,12 +3,20 @@ use bevy::{
     diagnostic::{Diagnostics, FrameTimeDiagnosticsPlugin},
     prelude::*,
 };
+use bevy::ecs::schedule::common_conditions::{in_state, not, resource_exists};
+use bevy_ggrs::AddRollbackCommandExtension;
 use bevy_turborand::prelude::*;
 use bevy_vector_shapes::prelude::*;
-const PLAYER_SIZE: f32 = 10f32;
+use std::time::Duration;
 
-const PLAYER_THRUST: f32 = 5.0;
-const PLAYER_TURN_SPEED: f32 = std::f32::consts::PI / 24.0;
+mod ai;
+mod net;
+mod physics;
+
+pub(crate) const PLAYER_SIZE: f32 = 10f32;
+
+pub(crate) const PLAYER_THRUST: f32 = 5.0;
+pub(crate) const PLAYER_TURN_SPEED: f32 = std::f32::consts::PI / 24.0;
 
 const PLAYER_SHOOT_DELAY: f32 = 0.5;
 
@@ -22,22 +30,64 @@ const ASTEROID_SPEED: f32 = 50.0;
 const ASTEROID_RADIUS: f32 = 20.0;
 const ASTEROID_COUNT: usize = 10;
 
-#[derive(Component, Debug, Default, PartialEq)]
-struct Velocity(Vec2);
+/// Gameplay runs at this fixed rate so physics and collisions are reproducible
+/// regardless of render frame rate. Render-only systems stay on `Update`.
+pub(crate) const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
+
+/// Cap on how many fixed steps can run in a single render frame, so a long
+/// hitch (asset load, window resize) can't spiral the sim further and further
+/// behind trying to catch up; the excess real time is simply dropped.
+const MAX_FIXED_STEPS_PER_FRAME: u32 = 5;
+
+/// (radius, speed) for each asteroid size, ordered largest to smallest.
+/// A destroyed asteroid spawns two children at the next stage, if any remain.
+const ASTEROID_STAGES: [(f32, f32); 3] = [
+    (ASTEROID_RADIUS, ASTEROID_SPEED),
+    (ASTEROID_RADIUS * 0.6, ASTEROID_SPEED * 1.5),
+    (ASTEROID_RADIUS * 0.3, ASTEROID_SPEED * 2.25),
+];
+
+/// Score awarded for destroying an asteroid at a given stage, smaller = harder to hit.
+const ASTEROID_STAGE_SCORE: [i32; 3] = [1, 2, 3];
+
+const STARTING_LIVES: i32 = 3;
+
+/// How long a respawned ship ignores asteroid contacts for.
+const INVULNERABILITY_SECONDS: f32 = 2.0;
+/// Half-period of the respawn-invulnerability blink effect.
+const INVULNERABILITY_BLINK_SECONDS: f32 = 0.15;
+
+/// Top-level game flow. Gameplay systems only run in [`GameState::Playing`];
+/// losing your last life drops the game into [`GameState::GameOver`] until
+/// the player asks for a new round.
+#[derive(States, Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) enum GameState {
+    #[default]
+    Playing,
+    GameOver,
+}
+
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct Velocity(pub(crate) Vec2);
 
 #[derive(Component)]
 struct Person;
 
-#[derive(Component, Debug, Eq, PartialEq)]
-struct Name(String);
+#[derive(Component, Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Name(String);
 
-#[derive(Component, Debug)]
-struct LimitedLifetime {
+#[derive(Component, Debug, Clone)]
+pub(crate) struct LimitedLifetime {
     timer: Timer,
 }
 
-#[derive(Component, Debug)]
-struct Bullet;
+#[derive(Component, Debug, Clone)]
+pub(crate) struct Bullet {
+    /// The ship that fired this bullet, so a kill can be credited to the
+    /// right scorer (the human player's [`Game`] vs. an AI ship's own
+    /// fitness) once it hits an asteroid.
+    owner: Entity,
+}
 
 #[derive(Bundle)]
 struct BulletBundle {
@@ -47,40 +97,78 @@ struct BulletBundle {
     limited_lifetime: LimitedLifetime,
 }
 
-#[derive(Component, Debug, Default)]
-struct Ship {
-    angle: f32,
-    thrusting: bool,
-    shoot_requested: bool,
-    shoot_timer: Timer,
+#[derive(Component, Debug, Default, Clone)]
+pub(crate) struct Ship {
+    pub(crate) angle: f32,
+    pub(crate) thrusting: bool,
+    pub(crate) shoot_requested: bool,
+    pub(crate) shoot_timer: Timer,
 }
 
 #[derive(Bundle)]
-struct PlayerBundle {
+pub(crate) struct PlayerBundle {
     name: Name,
     ship: Ship,
     position: TransformBundle,
     velocity: Velocity,
+    mass: physics::Mass,
+    force: physics::Force,
 }
 
-#[derive(Component, Debug)]
-struct Asteroid {
+impl PlayerBundle {
+    pub(crate) fn new(name: impl Into<String>, position: Vec3) -> Self {
+        Self {
+            name: Name(name.into()),
+            ship: Ship::default(),
+            position: TransformBundle::from_transform(Transform::from_translation(position)),
+            velocity: Velocity(Vec2::ZERO),
+            mass: physics::Mass::from_radius(PLAYER_SIZE),
+            force: physics::Force::default(),
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone)]
+pub(crate) struct Asteroid {
     radius: f32,
+    stage: usize,
 }
 
 #[derive(Bundle)]
 struct AsteroidBundle {
     position: TransformBundle,
     velocity: Velocity,
+    mass: physics::Mass,
+    force: physics::Force,
     size: Asteroid,
 }
 
-#[derive(Component, Debug, Default)]
-struct Game {
+#[derive(Component, Debug, Clone)]
+pub(crate) struct Game {
     // TODO render score in UI somewhere
-    score: i32,
+    pub(crate) score: i32,
+    pub(crate) lives: i32,
 }
 
+impl Game {
+    fn new() -> Self {
+        Self {
+            score: 0,
+            lives: STARTING_LIVES,
+        }
+    }
+}
+
+/// Marks a ship as temporarily immune to asteroid contacts after respawning,
+/// so a player isn't chain-killed while recovering at the origin.
+#[derive(Component, Debug, Clone)]
+pub(crate) struct Invulnerable {
+    timer: Timer,
+}
+
+#[derive(Component, Debug)]
+struct GameOverOverlay;
+
 #[derive(Bundle)]
 struct GameBundle {
     game: Game,
@@ -102,13 +190,33 @@ impl ScoreText {
 #[derive(Resource)]
 struct GreetTimer(Timer);
 
+/// Tracks how many fixed steps have already run this render frame, so the
+/// gameplay set can bail out once it hits `MAX_FIXED_STEPS_PER_FRAME`.
+#[derive(Resource, Default)]
+struct FixedStepsThisFrame(u32);
+
+/// All gameplay systems that must run deterministically in `FixedUpdate`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct GameplaySet;
+
+fn reset_fixed_steps_counter(mut steps: ResMut<FixedStepsThisFrame>) {
+    steps.0 = 0;
+}
+
+fn fixed_step_budget_available(mut steps: ResMut<FixedStepsThisFrame>) -> bool {
+    if steps.0 >= MAX_FIXED_STEPS_PER_FRAME {
+        return false;
+    }
+    steps.0 += 1;
+    true
+}
+
 fn setup(
     mut commands: Commands,
     mut windows: Query<&mut Window>,
     mut global_rng: ResMut<GlobalRng>,
+    netplay_active: Option<Res<net::NetplayActive>>,
 ) {
-    let mut rng = RngComponent::from(&mut global_rng);
-
     let mut window = windows.get_single_mut().unwrap();
     window.title = "Asteroids".to_string();
 
@@ -119,13 +227,37 @@ fn setup(
 
     commands.spawn(Camera2dBundle::default());
 
+    spawn_round(
+        &mut commands,
+        width,
+        height,
+        &mut global_rng,
+        netplay_active.is_some(),
+    );
+}
+
+/// Spawns a fresh asteroid field and [`Game`] tracker, plus the solo player's
+/// ship unless `netplay_active` — in netplay, [`net::spawn_player_ships`]
+/// spawns the two connected players' ships instead. Used both for the
+/// initial round in [`setup`] and to start a new round after a game over.
+/// When netplay is active, newly spawned entities are tagged for GGRS
+/// rollback so a resimulation actually restores their state.
+fn spawn_round(
+    commands: &mut Commands,
+    width: u32,
+    height: u32,
+    global_rng: &mut GlobalRng,
+    netplay_active: bool,
+) {
+    let mut rng = RngComponent::from(global_rng);
+
     let player_position = Vec3::new(0.0, 0.0, 0.0);
-    commands.spawn(PlayerBundle {
-        name: Name("Player".to_string()),
-        position: TransformBundle::from_transform(Transform::from_translation(player_position)),
-        velocity: Velocity(Vec2::new(5.0, 10.0)),
-        ship: Ship::default(),
-    });
+    if !netplay_active {
+        commands.spawn(PlayerBundle {
+            velocity: Velocity(Vec2::new(5.0, 10.0)),
+            ..PlayerBundle::new("Player", player_position)
+        });
+    }
 
     for _ in 0..ASTEROID_COUNT {
         let position = loop {
@@ -140,22 +272,26 @@ fn setup(
         };
 
         let angle = rng.f32() * std::f32::consts::PI * 2.0;
-        commands.spawn(AsteroidBundle {
+        let (radius, speed) = ASTEROID_STAGES[0];
+        let asteroid = commands.spawn(AsteroidBundle {
             position: TransformBundle::from_transform(Transform::from_translation(position)),
-            velocity: Velocity(Vec2::new(
-                angle.cos() * ASTEROID_SPEED,
-                angle.sin() * ASTEROID_SPEED,
-            )),
-            size: Asteroid {
-                radius: ASTEROID_RADIUS,
-            },
+            velocity: Velocity(Vec2::new(angle.cos() * speed, angle.sin() * speed)),
+            mass: physics::Mass::from_radius(radius),
+            force: physics::Force::default(),
+            size: Asteroid { radius, stage: 0 },
         });
+        if netplay_active {
+            asteroid.add_rollback();
+        }
     }
 
-    commands.spawn(GameBundle {
-        game: Game::default(),
+    let game = commands.spawn(GameBundle {
+        game: Game::new(),
         rng,
     });
+    if netplay_active {
+        game.add_rollback();
+    }
 }
 
 fn ui_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -210,27 +346,59 @@ fn quit_on_escape(keyboard_input: Res<Input<KeyCode>>, mut exit: EventWriter<App
     }
 }
 
-fn handle_input(keyboard_input: Res<Input<KeyCode>>, mut query: Query<(&mut Ship, &mut Velocity)>) {
-    for (ref mut ship, mut velocity) in query.iter_mut() {
-        if keyboard_input.pressed(KeyCode::A) {
-            ship.angle += PLAYER_TURN_SPEED;
-        }
-        if keyboard_input.pressed(KeyCode::D) {
-            ship.angle -= PLAYER_TURN_SPEED;
-        }
-        if keyboard_input.pressed(KeyCode::W) {
-            velocity.0 += Vec2::new(ship.angle.cos(), ship.angle.sin()) * PLAYER_THRUST;
-            ship.thrusting = true;
-        } else {
-            ship.thrusting = false;
-        }
-        ship.shoot_requested =
-            keyboard_input.pressed(KeyCode::Space) || keyboard_input.pressed(KeyCode::S);
+/// Samples the keyboard into a [`net::PlayerInput`] up front so the rest of
+/// gameplay (and the rollback netcode path in [`net`]) only ever deals with
+/// the compact, replayable input representation.
+fn handle_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    physics_config: Res<physics::PhysicsConfig>,
+    mut query: Query<(&mut Ship, &mut physics::Force), Without<ai::AiPilot>>,
+) {
+    let input = net::sample_keyboard(&keyboard_input);
+    for (mut ship, mut force) in query.iter_mut() {
+        apply_player_input(input, &physics_config, &mut ship, &mut force);
     }
 }
 
-fn draw_player(query: Query<(&Ship, &GlobalTransform)>, mut painter: ShapePainter) {
-    for (player, position) in &mut query.iter() {
+/// Applies one tick's worth of input to a single ship. Shared by the local
+/// [`handle_input`] system and the per-player rollback system in [`net`], so
+/// both paths move a ship identically regardless of where the input came from.
+/// Thrust is expressed as a force for [`physics::integrate_bodies`] to turn
+/// into an acceleration, rather than an instantaneous velocity change.
+pub(crate) fn apply_player_input(
+    input: net::PlayerInput,
+    physics_config: &physics::PhysicsConfig,
+    ship: &mut Ship,
+    force: &mut physics::Force,
+) {
+    if input.left() {
+        ship.angle += PLAYER_TURN_SPEED;
+    }
+    if input.right() {
+        ship.angle -= PLAYER_TURN_SPEED;
+    }
+    if input.thrust() {
+        force.0 += Vec2::new(ship.angle.cos(), ship.angle.sin()) * physics_config.thrust_force;
+        ship.thrusting = true;
+    } else {
+        ship.thrusting = false;
+    }
+    ship.shoot_requested = input.shoot();
+}
+
+fn draw_player(
+    query: Query<(&Ship, &GlobalTransform, Option<&Invulnerable>)>,
+    mut painter: ShapePainter,
+) {
+    for (player, position, invulnerable) in &mut query.iter() {
+        if let Some(invulnerable) = invulnerable {
+            let blink_phase =
+                (invulnerable.timer.elapsed_secs() / INVULNERABILITY_BLINK_SECONDS) as i32;
+            if blink_phase % 2 != 0 {
+                continue;
+            }
+        }
+
         painter.set_translation(position.translation());
         painter.color = Color::WHITE;
         painter.disable_laa = true;
@@ -282,33 +450,47 @@ fn draw_asteroids(query: Query<(&Asteroid, &GlobalTransform)>, mut painter: Shap
     }
 }
 
-fn despawn_timed_out_entities(
+pub(crate) fn despawn_timed_out_entities(
     mut commands: Commands,
-    time: Res<Time>,
     mut query: Query<(Entity, &mut LimitedLifetime)>,
 ) {
     for (entity, mut lifetime) in query.iter_mut() {
-        lifetime.timer.tick(time.delta());
+        lifetime.timer.tick(Duration::from_secs_f32(FIXED_TIMESTEP));
         if lifetime.timer.finished() {
             commands.entity(entity).despawn();
         }
     }
 }
 
-fn shoot(
+/// Counts down each ship's respawn invulnerability and lifts it once expired.
+pub(crate) fn tick_invulnerability(
     mut commands: Commands,
-    time: Res<Time>,
-    mut query: Query<(&mut Ship, &GlobalTransform, &Velocity)>,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in query.iter_mut() {
+        invulnerable
+            .timer
+            .tick(Duration::from_secs_f32(FIXED_TIMESTEP));
+        if invulnerable.timer.finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+pub(crate) fn shoot(
+    mut commands: Commands,
+    netplay_active: Option<Res<net::NetplayActive>>,
+    mut query: Query<(Entity, &mut Ship, &GlobalTransform, &Velocity)>,
 ) {
-    for (mut ship, transform, velocity) in query.iter_mut() {
-        ship.shoot_timer.tick(time.delta());
+    for (shooter, mut ship, transform, velocity) in query.iter_mut() {
+        ship.shoot_timer.tick(Duration::from_secs_f32(FIXED_TIMESTEP));
         if ship.shoot_requested {
             if ship.shoot_timer.finished() {
                 ship.shoot_timer = Timer::from_seconds(PLAYER_SHOOT_DELAY, TimerMode::Once);
                 ship.shoot_timer.reset();
 
-                commands.spawn(BulletBundle {
-                    bullet: Bullet,
+                let bullet = commands.spawn(BulletBundle {
+                    bullet: Bullet { owner: shooter },
                     position: TransformBundle::from_transform(
                         transform.compute_transform().clone(),
                     ),
@@ -323,41 +505,81 @@ fn shoot(
                         timer: Timer::from_seconds(BULLET_LIFETIME, TimerMode::Once),
                     },
                 });
+                if netplay_active.is_some() {
+                    bullet.add_rollback();
+                }
             }
         }
     }
 }
 
-fn move_objects(
-    time: Res<Time>,
-    mut query: Query<(&mut Transform, &Velocity)>,
-    windows: Query<&Window>,
+/// Spawns the two child fragments of a destroyed asteroid, each inheriting
+/// the parent's velocity plus a split velocity along a randomly perturbed
+/// angle, and a recoil impulse if the parent was hit with one. A no-op once
+/// the parent is already at the smallest stage.
+fn split_asteroid(
+    commands: &mut Commands,
+    rng: &mut RngComponent,
+    asteroid_transform: &Transform,
+    asteroid_velocity: Vec2,
+    asteroid: &Asteroid,
+    netplay_active: bool,
 ) {
-    let window = windows.get_single().unwrap();
-    let (half_width, half_height) = (window.width() / 2.0, window.height() / 2.0);
-
-    for (ref mut transform, velocity) in query.iter_mut() {
-        transform.translation += velocity.0.extend(0.0) * time.delta_seconds();
-
-        if transform.translation.x < -half_width || transform.translation.x > half_width {
-            transform.translation.x *= -1.0;
-        }
-        if transform.translation.y < -half_height || transform.translation.y > half_height {
-            transform.translation.y *= -1.0;
+    let Some(&(child_radius, child_speed)) = ASTEROID_STAGES.get(asteroid.stage + 1) else {
+        return;
+    };
+    let child_stage = asteroid.stage + 1;
+    for _ in 0..2 {
+        let spread_angle = (rng.f32() - 0.5) * std::f32::consts::FRAC_PI_2;
+        // A stationary parent has no heading to perturb, so give each child its
+        // own random base direction instead of collapsing both onto Vec2::ZERO.
+        let base_direction = asteroid_velocity
+            .try_normalize()
+            .unwrap_or_else(|| Vec2::from_angle(rng.f32() * std::f32::consts::TAU));
+        let split_velocity = Vec2::from_angle(spread_angle).rotate(base_direction) * child_speed;
+        let fragment = commands.spawn(AsteroidBundle {
+            position: TransformBundle::from_transform(*asteroid_transform),
+            velocity: Velocity(asteroid_velocity + split_velocity),
+            mass: physics::Mass::from_radius(child_radius),
+            force: physics::Force::default(),
+            size: Asteroid {
+                radius: child_radius,
+                stage: child_stage,
+            },
+        });
+        if netplay_active {
+            fragment.add_rollback();
         }
     }
 }
 
-fn check_collisions(
+pub(crate) fn check_collisions(
     mut commands: Commands,
-    mut game: Query<&mut Game>,
-    mut asteroid_query: Query<(Entity, &Transform, &Asteroid)>,
-    mut bullet_query: Query<(Entity, &Transform, &Bullet)>,
-    mut ship_query: Query<(Entity, &Transform, &Ship)>,
+    physics_config: Res<physics::PhysicsConfig>,
+    netplay_active: Option<Res<net::NetplayActive>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut game: Query<(&mut Game, &mut RngComponent)>,
+    mut asteroid_query: Query<(Entity, &Transform, &mut Velocity, &physics::Mass, &Asteroid)>,
+    bullet_query: Query<(Entity, &Transform, &Velocity, &Bullet)>,
+    mut ship_set: ParamSet<(
+        Query<(
+            Entity,
+            &mut Transform,
+            &Ship,
+            &mut Velocity,
+            &physics::Mass,
+            &mut physics::Force,
+            Option<&mut ai::AiPilot>,
+            Option<&Invulnerable>,
+        )>,
+        Query<&mut ai::AiPilot>,
+    )>,
 ) {
-    let mut game = game.single_mut();
-    for (asteroid_entity, asteroid_transform, asteroid) in asteroid_query.iter_mut() {
-        for (bullet_entity, bullet_transform, _bullet) in bullet_query.iter_mut() {
+    let (mut game, mut rng) = game.single_mut();
+    for (asteroid_entity, asteroid_transform, mut asteroid_velocity, asteroid_mass, asteroid) in
+        asteroid_query.iter_mut()
+    {
+        for (bullet_entity, bullet_transform, bullet_velocity, bullet) in bullet_query.iter() {
             if asteroid_transform
                 .translation
                 .distance(bullet_transform.translation)
@@ -365,20 +587,100 @@ fn check_collisions(
             {
                 commands.entity(asteroid_entity).despawn();
                 commands.entity(bullet_entity).despawn();
-                game.score += 1;
+                let points = ASTEROID_STAGE_SCORE[asteroid.stage];
+                match ship_set.p1().get_mut(bullet.owner) {
+                    // AI kills are credited to that ship's own fitness, not the
+                    // human player's score, so evolution selects for actually
+                    // destroying asteroids without polluting the displayed score.
+                    Ok(mut pilot) => ai::credit_kill(&mut pilot, points),
+                    Err(_) => game.score += points,
+                }
+                // The bullet is about to despawn, so only its recoil onto the
+                // asteroid matters here — same impulse maths as a ship hit.
+                let (_, asteroid_delta) = physics::resolve_contact(
+                    &physics_config,
+                    bullet_transform.translation.truncate(),
+                    bullet_velocity.0,
+                    physics::Mass::from_radius(BULLET_RADIUS).0,
+                    asteroid_transform.translation.truncate(),
+                    asteroid_velocity.0,
+                    asteroid_mass.0,
+                );
+                asteroid_velocity.0 += asteroid_delta;
+                split_asteroid(
+                    &mut commands,
+                    &mut rng,
+                    asteroid_transform,
+                    asteroid_velocity.0,
+                    asteroid,
+                    netplay_active.is_some(),
+                );
             }
         }
 
-        for (ship_entity, ship_transform, _ship) in ship_query.iter_mut() {
+        for (
+            ship_entity,
+            mut ship_transform,
+            _ship,
+            mut ship_velocity,
+            ship_mass,
+            mut ship_force,
+            ai_pilot,
+            invulnerable,
+        ) in ship_set.p0().iter_mut()
+        {
+            if invulnerable.is_some() {
+                continue;
+            }
+
             if asteroid_transform
                 .translation
                 .distance(ship_transform.translation)
                 < asteroid.radius + PLAYER_SIZE
             {
-                // TODO end game and restart it
+                let (ship_delta, asteroid_delta) = physics::resolve_contact(
+                    &physics_config,
+                    ship_transform.translation.truncate(),
+                    ship_velocity.0,
+                    ship_mass.0,
+                    asteroid_transform.translation.truncate(),
+                    asteroid_velocity.0,
+                    asteroid_mass.0,
+                );
+                ship_velocity.0 += ship_delta;
+                asteroid_velocity.0 += asteroid_delta;
+
                 commands.entity(asteroid_entity).despawn();
-                commands.entity(ship_entity).despawn();
-                game.score -= 1;
+                split_asteroid(
+                    &mut commands,
+                    &mut rng,
+                    asteroid_transform,
+                    asteroid_velocity.0,
+                    asteroid,
+                    netplay_active.is_some(),
+                );
+
+                match ai_pilot {
+                    // AI pilots are evaluated by their final fitness, so freeze them
+                    // in place rather than bouncing them until the generation ends.
+                    Some(mut ai_pilot) => ai::kill_ai_pilot(&mut ai_pilot, &mut ship_velocity),
+                    None => {
+                        game.lives -= 1;
+                        if game.lives > 0 {
+                            ship_transform.translation = Vec3::ZERO;
+                            ship_velocity.0 = Vec2::ZERO;
+                            ship_force.0 = Vec2::ZERO;
+                            commands.entity(ship_entity).insert(Invulnerable {
+                                timer: Timer::from_seconds(
+                                    INVULNERABILITY_SECONDS,
+                                    TimerMode::Once,
+                                ),
+                            });
+                        } else {
+                            next_game_state.set(GameState::GameOver);
+                        }
+                    }
+                }
             }
         }
     }
@@ -412,23 +714,119 @@ fn update_score_text(
     }
 }
 
+fn spawn_game_over_overlay(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game: Query<&Game>,
+) {
+    let style = TextStyle {
+        font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+        font_size: 48.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::all(Val::Percent(100.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            GameOverOverlay,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_sections([
+                TextSection::new("Game Over\n", style.clone()),
+                TextSection::new(format!("Final score: {}\n", game.single().score), style.clone()),
+                TextSection::new("Press any key to play again", style),
+            ])
+            .with_text_alignment(TextAlignment::Center));
+        });
+}
+
+fn despawn_game_over_overlay(mut commands: Commands, query: Query<Entity, With<GameOverOverlay>>) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Waits for any key press in [`GameState::GameOver`], clears the old round's
+/// entities, and spawns a fresh one before handing control back to `Playing`.
+/// Solo-play restart: polls the local keyboard directly. Disabled whenever
+/// [`net::NetplayActive`] is present — a netplay restart must happen on the
+/// same confirmed tick for both peers, so that path is handled instead by
+/// [`net::restart_on_synced_input`] off the synchronized GGRS input stream.
+fn restart_on_key_press(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut windows: Query<&mut Window>,
+    mut global_rng: ResMut<GlobalRng>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    stale_entities: Query<Entity, Or<(With<Asteroid>, With<Bullet>, With<Ship>, With<Game>)>>,
+) {
+    if keyboard_input.get_just_pressed().next().is_none() {
+        return;
+    }
+
+    for entity in &stale_entities {
+        commands.entity(entity).despawn();
+    }
+
+    let window = windows.get_single_mut().unwrap();
+    let (width, height) = (window.physical_width(), window.physical_height());
+    spawn_round(&mut commands, width, height, &mut global_rng, false);
+
+    next_game_state.set(GameState::Playing);
+}
+
 pub struct AsteroidsPlugin;
 
 impl Plugin for AsteroidsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(GreetTimer(Timer::from_seconds(2.0, TimerMode::Repeating)))
+            .insert_resource(FixedTime::new_from_secs(FIXED_TIMESTEP))
+            .init_resource::<FixedStepsThisFrame>()
             .add_plugin(RngPlugin::default())
             .add_plugin(FrameTimeDiagnosticsPlugin::default())
+            .add_state::<GameState>()
             .add_startup_system(setup)
             .add_startup_system(ui_setup)
-            .add_system(handle_input)
+            .add_system(reset_fixed_steps_counter.in_base_set(CoreSet::First))
+            .configure_set(
+                GameplaySet
+                    .in_schedule(CoreSchedule::FixedUpdate)
+                    .run_if(fixed_step_budget_available)
+                    .run_if(not(resource_exists::<net::NetplayActive>()))
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                (
+                    handle_input,
+                    tick_invulnerability,
+                    physics::integrate_bodies,
+                    physics::move_kinematic_bodies,
+                    shoot,
+                    check_collisions,
+                    despawn_timed_out_entities,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            )
+            .add_system(spawn_game_over_overlay.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(despawn_game_over_overlay.in_schedule(OnExit(GameState::GameOver)))
+            .add_system(
+                restart_on_key_press
+                    .run_if(not(resource_exists::<net::NetplayActive>()))
+                    .in_set(OnUpdate(GameState::GameOver)),
+            )
             .add_system(update_fps_text)
             .add_system(update_score_text)
             .add_system(quit_on_escape)
-            .add_system(move_objects)
-            .add_system(despawn_timed_out_entities)
-            .add_system(shoot)
-            .add_system(check_collisions)
             .add_system(draw_bullets)
             .add_system(draw_asteroids)
             .add_system(draw_player);
@@ -440,5 +838,8 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugin(Shape2dPlugin::default())
         .add_plugin(AsteroidsPlugin)
+        .add_plugin(physics::PhysicsPlugin)
+        .add_plugin(ai::AiPlugin)
+        .add_plugin(net::NetPlugin)
         .run();
 }
@@ -0,0 +1,299 @@
+//! Optional two-player rollback netcode, built on top of the fixed-timestep
+//! gameplay schedule. Disabled unless both `--local-port` and `--remote-addr`
+//! are passed on the command line; a single-player game never touches this
+//! module's systems.
+
+use bevy::ecs::schedule::common_conditions::in_state;
+use bevy::prelude::*;
+use bevy_ggrs::{ggrs, AddRollbackCommandExtension, GgrsApp, GgrsPlugin, GgrsSchedule, PlayerInputs, Session};
+use bevy_turborand::prelude::*;
+use ggrs::{Config, PlayerType, SessionBuilder, UdpNonBlockingSocket};
+
+use crate::{
+    apply_player_input, check_collisions, despawn_timed_out_entities, physics, shoot,
+    spawn_round, tick_invulnerability, Asteroid, Bullet, Game, GameState, Invulnerable,
+    LimitedLifetime, PlayerBundle, Ship, Velocity, FIXED_TIMESTEP,
+};
+
+const INPUT_LEFT: u8 = 1 << 0;
+const INPUT_RIGHT: u8 = 1 << 1;
+const INPUT_THRUST: u8 = 1 << 2;
+const INPUT_SHOOT: u8 = 1 << 3;
+const INPUT_RESTART: u8 = 1 << 4;
+
+/// Packed local input for one player, small and `Pod` so GGRS can ship it
+/// over the wire and replay it byte-for-byte during a rollback.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct PlayerInput(u8);
+
+impl PlayerInput {
+    pub(crate) fn left(self) -> bool {
+        self.0 & INPUT_LEFT != 0
+    }
+    pub(crate) fn right(self) -> bool {
+        self.0 & INPUT_RIGHT != 0
+    }
+    pub(crate) fn thrust(self) -> bool {
+        self.0 & INPUT_THRUST != 0
+    }
+    pub(crate) fn shoot(self) -> bool {
+        self.0 & INPUT_SHOOT != 0
+    }
+    pub(crate) fn restart(self) -> bool {
+        self.0 & INPUT_RESTART != 0
+    }
+}
+
+/// Samples the keyboard into a [`PlayerInput`] bitfield, used both for local
+/// single-player ships and as this machine's input into a rollback session.
+pub(crate) fn sample_keyboard(keyboard_input: &Input<KeyCode>) -> PlayerInput {
+    let mut bits = 0u8;
+    if keyboard_input.pressed(KeyCode::A) {
+        bits |= INPUT_LEFT;
+    }
+    if keyboard_input.pressed(KeyCode::D) {
+        bits |= INPUT_RIGHT;
+    }
+    if keyboard_input.pressed(KeyCode::W) {
+        bits |= INPUT_THRUST;
+    }
+    if keyboard_input.pressed(KeyCode::Space) || keyboard_input.pressed(KeyCode::S) {
+        bits |= INPUT_SHOOT;
+    }
+    if keyboard_input.get_just_pressed().next().is_some() {
+        bits |= INPUT_RESTART;
+    }
+    PlayerInput(bits)
+}
+
+pub(crate) struct GgrsConfig;
+
+impl Config for GgrsConfig {
+    type Input = PlayerInput;
+    type State = u8;
+    type Address = std::net::SocketAddr;
+}
+
+/// Present as a resource exactly when a rollback session is active, so the
+/// plain `FixedUpdate` gameplay set can step aside and let [`GgrsSchedule`]
+/// drive the same systems instead.
+#[derive(Resource)]
+pub(crate) struct NetplayActive;
+
+/// Tags which connected player a ship belongs to.
+#[derive(Component)]
+struct Player {
+    handle: usize,
+}
+
+pub(crate) struct NetArgs {
+    local_port: u16,
+    pub(crate) local_handle: usize,
+    remote_addr: std::net::SocketAddr,
+    pub(crate) remote_handle: usize,
+}
+
+/// Parses `--local-port <port> --remote-addr <ip:port>` from argv. Returns
+/// `None`, and the game stays local, unless both are supplied and parse.
+fn parse_net_args() -> Option<NetArgs> {
+    let args: Vec<String> = std::env::args().collect();
+    let local_port = args
+        .iter()
+        .position(|a| a == "--local-port")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|p| p.parse().ok())?;
+    let remote_addr = args
+        .iter()
+        .position(|a| a == "--remote-addr")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|a| a.parse().ok())?;
+    // Lower port number plays as handle 0, by convention, so peers on distinct
+    // ports agree on player ordering without an extra handshake. Equal ports
+    // are not told apart: both peers then take handle 1.
+    let (local_handle, remote_handle) = if local_port < remote_addr.port() {
+        (0, 1)
+    } else {
+        (1, 0)
+    };
+    Some(NetArgs {
+        local_port,
+        local_handle,
+        remote_addr,
+        remote_handle,
+    })
+}
+
+fn read_local_input(keyboard_input: Res<Input<KeyCode>>) -> PlayerInput {
+    sample_keyboard(&keyboard_input)
+}
+
+fn spawn_players(mut commands: Commands, net_args: Res<NetArgsResource>) {
+    spawn_player_ships(&mut commands, &net_args.0);
+}
+
+/// Spawns both connected players' ships, tagged for GGRS rollback. Shared by
+/// the initial [`spawn_players`] startup system and [`restart_on_synced_input`]
+/// so a post-game-over round gets both players back instead of the solo ship.
+pub(crate) fn spawn_player_ships(commands: &mut Commands, net_args: &NetArgs) {
+    for (handle, x) in [
+        (net_args.local_handle, -PLAYER_SPACING),
+        (net_args.remote_handle, PLAYER_SPACING),
+    ] {
+        commands
+            .spawn(PlayerBundle::new(
+                format!("Player {handle}"),
+                Vec3::new(x, 0.0, 0.0),
+            ))
+            .insert(Player { handle })
+            .add_rollback();
+    }
+}
+
+const PLAYER_SPACING: f32 = 50.0;
+
+/// Per-player analog of [`crate::handle_input`]: reads this tick's rolled
+/// back input for each connected player and applies it to that player's ship.
+fn apply_rollback_input(
+    inputs: Res<PlayerInputs<GgrsConfig>>,
+    physics_config: Res<physics::PhysicsConfig>,
+    mut query: Query<(&Player, &mut Ship, &mut physics::Force)>,
+) {
+    for (player, mut ship, mut force) in query.iter_mut() {
+        let (input, _status) = inputs[player.handle];
+        apply_player_input(input, &physics_config, &mut ship, &mut force);
+    }
+}
+
+/// Netplay analog of [`crate::restart_on_key_press`]: a restart must happen
+/// on the same confirmed tick for both peers, so this reads the restart bit
+/// out of the synchronized (rolled-back) input stream instead of polling the
+/// local keyboard directly — otherwise each peer would reseed its own round
+/// the instant its own player pressed a key, desyncing the asteroid field.
+fn restart_on_synced_input(
+    mut commands: Commands,
+    inputs: Res<PlayerInputs<GgrsConfig>>,
+    net_args: Res<NetArgsResource>,
+    mut windows: Query<&mut Window>,
+    mut global_rng: ResMut<GlobalRng>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    stale_entities: Query<Entity, Or<(With<Asteroid>, With<Bullet>, With<Ship>, With<Game>)>>,
+) {
+    let restart_requested = [net_args.0.local_handle, net_args.0.remote_handle]
+        .into_iter()
+        .any(|handle| inputs[handle].0.restart());
+    if !restart_requested {
+        return;
+    }
+
+    for entity in &stale_entities {
+        commands.entity(entity).despawn();
+    }
+
+    let window = windows.get_single_mut().unwrap();
+    let (width, height) = (window.physical_width(), window.physical_height());
+    spawn_round(&mut commands, width, height, &mut global_rng, true);
+    spawn_player_ships(&mut commands, &net_args.0);
+
+    next_game_state.set(GameState::Playing);
+}
+
+#[derive(Resource)]
+pub(crate) struct NetArgsResource(pub(crate) NetArgs);
+
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(net_args) = parse_net_args() else {
+            return;
+        };
+
+        let socket = UdpNonBlockingSocket::bind_to_port(net_args.local_port)
+            .expect("failed to bind local UDP socket for rollback session");
+        let mut builder = SessionBuilder::<GgrsConfig>::new()
+            .with_num_players(2)
+            .with_fps(FIXED_TIMESTEP.recip().round() as usize)
+            .expect("fixed timestep does not divide into a valid FPS for GGRS");
+        builder = builder
+            .add_player(PlayerType::Local, net_args.local_handle)
+            .expect("failed to register local player");
+        builder = builder
+            .add_player(PlayerType::Remote(net_args.remote_addr), net_args.remote_handle)
+            .expect("failed to register remote player");
+        let session = builder
+            .start_p2p_session(socket)
+            .expect("failed to start p2p rollback session");
+
+        app.add_plugin(GgrsPlugin::<GgrsConfig>::default())
+            .rollback_component_with_clone::<Velocity>()
+            .rollback_component_with_clone::<physics::Force>()
+            .rollback_component_with_clone::<physics::Mass>()
+            .rollback_component_with_clone::<Transform>()
+            .rollback_component_with_clone::<Ship>()
+            .rollback_component_with_clone::<Game>()
+            .rollback_component_with_clone::<Invulnerable>()
+            .rollback_component_with_clone::<Asteroid>()
+            .rollback_component_with_clone::<Bullet>()
+            .rollback_component_with_clone::<LimitedLifetime>()
+            .rollback_component_with_clone::<RngComponent>()
+            .set_rollback_schedule_fps((FIXED_TIMESTEP.recip().round()) as usize)
+            .insert_resource(NetArgsResource(net_args))
+            .insert_resource(NetplayActive)
+            .insert_resource(Session::P2P(session))
+            .add_startup_system(spawn_players)
+            .add_system(read_local_input.in_schedule(bevy_ggrs::ReadInputs))
+            .add_systems(
+                (
+                    apply_rollback_input,
+                    tick_invulnerability,
+                    physics::integrate_bodies,
+                    physics::move_kinematic_bodies,
+                    shoot,
+                    check_collisions,
+                    despawn_timed_out_entities,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing))
+                    .in_schedule(GgrsSchedule),
+            )
+            .add_system(
+                restart_on_synced_input
+                    .run_if(in_state(GameState::GameOver))
+                    .in_schedule(GgrsSchedule),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_input_has_no_bits_set() {
+        let input = PlayerInput::default();
+        assert!(!input.left());
+        assert!(!input.right());
+        assert!(!input.thrust());
+        assert!(!input.shoot());
+    }
+
+    #[test]
+    fn each_bit_round_trips_independently() {
+        assert!(PlayerInput(INPUT_LEFT).left());
+        assert!(!PlayerInput(INPUT_LEFT).right());
+        assert!(PlayerInput(INPUT_RIGHT).right());
+        assert!(!PlayerInput(INPUT_RIGHT).left());
+        assert!(PlayerInput(INPUT_THRUST).thrust());
+        assert!(PlayerInput(INPUT_SHOOT).shoot());
+    }
+
+    #[test]
+    fn combined_bits_are_all_readable() {
+        let input = PlayerInput(INPUT_LEFT | INPUT_THRUST);
+        assert!(input.left());
+        assert!(input.thrust());
+        assert!(!input.right());
+        assert!(!input.shoot());
+    }
+}
@@ -0,0 +1,187 @@
+//! Minimal rigid-body layer: mass-proportional-to-radius bodies accumulate
+//! forces over a tick, which are integrated into velocity and then position
+//! in `FixedUpdate`, ahead of collision handling. Screen wrap-around is kept
+//! as a teleport that preserves velocity, same as the old arcade feel.
+
+use bevy::prelude::*;
+
+use crate::{Velocity, FIXED_TIMESTEP};
+
+/// Tunables for the physics step. Defaults reproduce the original arcade
+/// feel: no damping, fully elastic bounces, and a `thrust_force` rescaled so
+/// that `force / mass * FIXED_TIMESTEP` still adds `PLAYER_THRUST` to
+/// velocity per tick, same as the old direct `velocity += PLAYER_THRUST`.
+#[derive(Resource, Debug, Clone, Copy)]
+pub(crate) struct PhysicsConfig {
+    pub(crate) linear_damping: f32,
+    pub(crate) restitution: f32,
+    pub(crate) thrust_force: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            linear_damping: 0.0,
+            restitution: 1.0,
+            thrust_force: crate::PLAYER_THRUST * crate::PLAYER_SIZE / crate::FIXED_TIMESTEP,
+        }
+    }
+}
+
+/// Mass proportional to collider radius, used to turn a [`Force`] into an
+/// acceleration and to weigh collision impulses.
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct Mass(pub(crate) f32);
+
+impl Mass {
+    pub(crate) fn from_radius(radius: f32) -> Self {
+        Self(radius)
+    }
+}
+
+/// This tick's accumulated force; zeroed once [`integrate_bodies`] consumes it.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub(crate) struct Force(pub(crate) Vec2);
+
+/// Integrates forces into velocity (with damping) and velocity into
+/// position for every rigid body (ships, asteroids), then wraps it across
+/// screen edges.
+pub(crate) fn integrate_bodies(
+    config: Res<PhysicsConfig>,
+    mut query: Query<(&mut Transform, &mut Velocity, &Mass, &mut Force)>,
+    windows: Query<&Window>,
+) {
+    let window = windows.get_single().unwrap();
+    let (half_width, half_height) = (window.width() / 2.0, window.height() / 2.0);
+
+    for (mut transform, mut velocity, mass, mut force) in query.iter_mut() {
+        velocity.0 += force.0 / mass.0 * FIXED_TIMESTEP;
+        force.0 = Vec2::ZERO;
+        velocity.0 *= 1.0 - config.linear_damping;
+
+        transform.translation += velocity.0.extend(0.0) * FIXED_TIMESTEP;
+        wrap_around_screen(&mut transform, half_width, half_height);
+    }
+}
+
+/// Bullets aren't rigid bodies (no mass, no thrust) so they just fly in a
+/// straight line and wrap the same way bodies do.
+pub(crate) fn move_kinematic_bodies(
+    mut query: Query<(&mut Transform, &Velocity), Without<Mass>>,
+    windows: Query<&Window>,
+) {
+    let window = windows.get_single().unwrap();
+    let (half_width, half_height) = (window.width() / 2.0, window.height() / 2.0);
+
+    for (mut transform, velocity) in query.iter_mut() {
+        transform.translation += velocity.0.extend(0.0) * FIXED_TIMESTEP;
+        wrap_around_screen(&mut transform, half_width, half_height);
+    }
+}
+
+fn wrap_around_screen(transform: &mut Transform, half_width: f32, half_height: f32) {
+    if transform.translation.x < -half_width || transform.translation.x > half_width {
+        transform.translation.x *= -1.0;
+    }
+    if transform.translation.y < -half_height || transform.translation.y > half_height {
+        transform.translation.y *= -1.0;
+    }
+}
+
+/// Resolves a circle/circle contact into an impulse along the collision
+/// normal, using standard elastic-collision-with-restitution maths. Returns
+/// the velocity deltas to apply to `a` and `b` respectively.
+pub(crate) fn resolve_contact(
+    config: &PhysicsConfig,
+    position_a: Vec2,
+    velocity_a: Vec2,
+    mass_a: f32,
+    position_b: Vec2,
+    velocity_b: Vec2,
+    mass_b: f32,
+) -> (Vec2, Vec2) {
+    let normal = (position_a - position_b).normalize_or_zero();
+    let relative_velocity = velocity_a - velocity_b;
+    let closing_speed = relative_velocity.dot(normal);
+    if closing_speed >= 0.0 {
+        // Already separating; nothing to resolve.
+        return (Vec2::ZERO, Vec2::ZERO);
+    }
+
+    let impulse_magnitude =
+        -(1.0 + config.restitution) * closing_speed / (1.0 / mass_a + 1.0 / mass_b);
+    let impulse = normal * impulse_magnitude;
+    (impulse / mass_a, -impulse / mass_b)
+}
+
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsConfig>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_contact_head_on_equal_mass_swaps_velocities() {
+        let config = PhysicsConfig {
+            linear_damping: 0.0,
+            restitution: 1.0,
+            thrust_force: 0.0,
+        };
+        let (delta_a, delta_b) = resolve_contact(
+            &config,
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            1.0,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(-1.0, 0.0),
+            1.0,
+        );
+        assert!((delta_a - Vec2::new(-2.0, 0.0)).length() < 1e-5);
+        assert!((delta_b - Vec2::new(2.0, 0.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn resolve_contact_separating_bodies_are_untouched() {
+        let config = PhysicsConfig {
+            linear_damping: 0.0,
+            restitution: 1.0,
+            thrust_force: 0.0,
+        };
+        let (delta_a, delta_b) = resolve_contact(
+            &config,
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(-1.0, 0.0),
+            1.0,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            1.0,
+        );
+        assert_eq!(delta_a, Vec2::ZERO);
+        assert_eq!(delta_b, Vec2::ZERO);
+    }
+
+    #[test]
+    fn resolve_contact_heavier_body_gets_smaller_velocity_change() {
+        let config = PhysicsConfig {
+            linear_damping: 0.0,
+            restitution: 1.0,
+            thrust_force: 0.0,
+        };
+        let (delta_a, delta_b) = resolve_contact(
+            &config,
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            1.0,
+            Vec2::new(1.0, 0.0),
+            Vec2::new(-1.0, 0.0),
+            10.0,
+        );
+        assert!(delta_a.length() > delta_b.length());
+    }
+}